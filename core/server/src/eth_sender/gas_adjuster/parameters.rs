@@ -3,67 +3,677 @@
 //! Currently the following types of parameters are provided:
 //! - Maximum gas price renewal interval: interval between updates of the upper limit for
 //!   gas price suggested by `GasAdjuster`.
-//! - Maximum gas price scale: multiplier to be applied to the average gas price to
-//!   calculate the upper limit for gas price in `GasAdjuster`.
+//! - Maximum gas price multiplier: validated multiplier (`>= 1.0`) applied to the average
+//!   gas price to calculate the upper limit for gas price in `GasAdjuster`.
+//! - Maximum gas price limit: the upper limit for the gas price, optionally calibrated
+//!   from a target transaction cost expressed in USD rather than a bare multiplier.
+//! - Minimum gas price: the lower bound for the suggested gas price, so that suggested
+//!   prices don't fall below the node's own relay policy during low-fee periods.
+//! - Adaptive price: a profitability-driven alternative to the static multiplier that nudges
+//!   the suggested price toward one that just covers realized L1 costs.
+//! - Pubdata price: a parallel set of interval/scale/floor parameters for the fair pubdata
+//!   price, so the adjuster can react to L1 data-posting costs independently of execution gas.
 //!
 //! The module uses a child module `parameters_impl` which contains two implementations
 //! for functions declared in module: one for the actual usage, and one for tests.
 //! While the actual implementation obtains the values from the environment variables,
 //! the test one uses hard-coded values for better test behavior predictability.
+//!
+//! The values that may be retuned at runtime are kept in a thread-safe `ParametersStore`
+//! seeded from `parameters_impl` at startup. The getters below read through the store, and
+//! the `set_*` functions mutate it, so an administrator can reconfigure the adjuster over an
+//! authenticated admin RPC method without restarting the node to re-read the environment.
 
 // Built-in deps.
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, RwLock, OnceLock};
 use std::time::Duration;
+// Workspace deps
+use web3::types::U256;
+
+/// Runtime-mutable snapshot of the `GasAdjuster` parameters.
+///
+/// Seeded from the environment (or the hard-coded test values) on first access, it can then
+/// be mutated in place through the `set_*` functions without restarting the process.
+#[derive(Debug, Clone)]
+pub struct ParametersStore {
+    /// Interval between renewals of the maximum gas price.
+    pub max_price_interval: Duration,
+    /// Multiplier applied to the average gas price to obtain the upper limit.
+    pub max_price_multiplier: f64,
+    /// Lower bound for the suggested gas price (zero means "no floor").
+    pub min_price: U256,
+    /// Interval between renewals of the maximum pubdata price.
+    pub max_pubdata_price_interval: Duration,
+    /// Multiplier applied to the average pubdata price to obtain the upper limit.
+    pub max_pubdata_price_scale: f64,
+    /// Lower bound for the suggested pubdata price.
+    pub min_pubdata_price: U256,
+}
+
+impl ParametersStore {
+    /// Seeds the store from the configured source (`parameters_impl`).
+    fn from_config() -> Self {
+        Self {
+            max_price_interval: parameters_impl::get_max_price_interval(),
+            // A misconfigured multiplier is a fatal start-up error, so reject it loudly here;
+            // runtime updates (see `set_max_price_multiplier`) surface the error instead.
+            max_price_multiplier: validate_multiplier(parameters_impl::get_max_price_multiplier())
+                .expect("Invalid gas price multiplier in configuration"),
+            min_price: parameters_impl::get_min_price(),
+            max_pubdata_price_interval: parameters_impl::get_max_pubdata_price_interval(),
+            max_pubdata_price_scale: validate_multiplier(
+                parameters_impl::get_max_pubdata_price_scale(),
+            )
+            .expect("Invalid pubdata price multiplier in configuration"),
+            min_pubdata_price: parameters_impl::get_min_pubdata_price(),
+        }
+    }
+}
+
+/// Fields of an admin reconfiguration request; `None` leaves the corresponding value
+/// untouched.
+#[derive(Debug, Default, Clone)]
+pub struct ParametersUpdate {
+    /// New scaling factor for the maximum gas price.
+    pub max_price_multiplier: Option<f64>,
+    /// New renewal interval, in seconds.
+    pub max_price_interval_secs: Option<u64>,
+    /// New lower bound for the suggested gas price, in wei.
+    pub min_price: Option<U256>,
+}
+
+static STORE: OnceLock<Arc<RwLock<ParametersStore>>> = OnceLock::new();
+
+/// Returns the shared parameters store, seeding it from the configuration on first access.
+///
+/// The returned `Arc` can be cloned and handed to the admin RPC layer so that it mutates the
+/// same instance the `GasAdjuster` reads from.
+pub fn shared_store() -> Arc<RwLock<ParametersStore>> {
+    STORE
+        .get_or_init(|| Arc::new(RwLock::new(ParametersStore::from_config())))
+        .clone()
+}
 
 /// Obtains the interval for renewing the maximum gas price.
 ///
-/// This value is not cached internally, as it may be changed for the already running
-/// server by an administrator. This may be required if existing settings aren't flexible
-/// enough to match the current network price.
+/// This value is read through the `ParametersStore`, so it reflects any runtime
+/// reconfiguration performed by an administrator. This may be required if existing settings
+/// aren't flexible enough to match the current network price.
 pub fn get_max_price_interval() -> Duration {
-    parameters_impl::get_max_price_interval()
+    shared_store().read().unwrap().max_price_interval
+}
+
+/// Obtains the multiplier for the maximum gas price.
+///
+/// The returned value is always `>= 1.0`: a multiplier of `1.0` means "use the sampled
+/// average as-is, with no headroom", while larger values add headroom on top. Values below
+/// `1.0` (which would cap the limit *below* the observed average and reject healthy
+/// transactions) are rejected when the parameter is loaded.
+///
+/// This value is read through the `ParametersStore`, so it reflects any runtime
+/// reconfiguration performed by an administrator. This may be required if existing settings
+/// aren't flexible enough to match the current network price.
+pub fn get_max_price_multiplier() -> f64 {
+    shared_store().read().unwrap().max_price_multiplier
+}
+
+/// Sets the interval for renewing the maximum gas price at runtime.
+pub fn set_max_price_interval(interval: Duration) {
+    shared_store().write().unwrap().max_price_interval = interval;
+}
+
+/// Sets the multiplier for the maximum gas price at runtime.
+///
+/// Returns an error (leaving the stored value untouched) if `multiplier` is NaN or below the
+/// `1.0` lower bound, so a bad admin request is reported to the RPC caller rather than
+/// aborting the handler.
+pub fn set_max_price_multiplier(multiplier: f64) -> Result<(), String> {
+    let multiplier = validate_multiplier(multiplier)?;
+    shared_store().write().unwrap().max_price_multiplier = multiplier;
+    Ok(())
+}
+
+/// Validates a gas-price multiplier, enforcing the `1.0` lower bound.
+///
+/// A multiplier of `1.0` means "use the average as-is, no headroom"; anything below that
+/// (including NaN or negative values) would set the upper limit beneath the observed average
+/// and is rejected with a clear error.
+fn validate_multiplier(multiplier: f64) -> Result<f64, String> {
+    if multiplier.is_finite() && multiplier >= 1.0 {
+        Ok(multiplier)
+    } else {
+        Err(format!(
+            "Price multiplier must be a finite value >= 1.0, got {}",
+            multiplier
+        ))
+    }
+}
+
+/// Sets the lower bound for the suggested gas price at runtime.
+pub fn set_min_price(min_price: U256) {
+    shared_store().write().unwrap().min_price = min_price;
+}
+
+/// Applies a reconfiguration received over the authenticated admin RPC method.
+///
+/// The RPC method itself (including its authentication and registration) lives in the API
+/// server and is out of scope for this module; this function is the entry point it calls to
+/// map a decoded request onto the shared store. Validation errors are returned so the RPC
+/// handler can reply with a fault instead of aborting. Only the multiplier can be rejected, and
+/// it is checked before any other field is applied, so a rejected request leaves every value
+/// unchanged.
+pub fn apply_admin_update(update: ParametersUpdate) -> Result<(), String> {
+    if let Some(multiplier) = update.max_price_multiplier {
+        set_max_price_multiplier(multiplier)?;
+    }
+    if let Some(secs) = update.max_price_interval_secs {
+        set_max_price_interval(Duration::from_secs(secs));
+    }
+    if let Some(min_price) = update.min_price {
+        set_min_price(min_price);
+    }
+    Ok(())
+}
+
+/// Obtains the upper limit for the gas price for a transaction expected to consume
+/// `gas_used` units of gas.
+///
+/// Depending on the configured mode (see `ETH_MAX_GAS_PRICE_MODE`) the limit is either
+/// derived from the multiplicative scaling (in which case no fiat cap applies and
+/// `U256::max_value()` is returned, letting the caller keep using `get_max_price_multiplier`
+/// against the sampled average) or calibrated from a target per-transaction cost
+/// expressed in USD. The latter converts the USD budget and a live ETH/USD rate into a
+/// wei-denominated per-gas ceiling, which keeps fees bounded in fiat terms during
+/// volatile ETH price swings.
+///
+/// The limit is recomputed on every call (the USD-mode path reads the live ETH/USD quote), so
+/// it is not served through the runtime-mutable `ParametersStore`.
+pub fn get_max_price_limit(gas_used: U256) -> U256 {
+    parameters_impl::get_max_price_limit(gas_used)
+}
+
+/// Obtains the lower bound for the suggested gas price.
+///
+/// A value of zero means "no floor": the suggested price is not clamped from below.
+/// Callers should clamp the price they suggest into the `[min, max]` band.
+///
+/// This value is read through the `ParametersStore`, so it reflects any runtime
+/// reconfiguration performed by an administrator. This may be required if existing settings
+/// aren't flexible enough to match the current network price.
+pub fn get_min_price() -> U256 {
+    shared_store().read().unwrap().min_price
+}
+
+/// Obtains the interval for renewing the maximum pubdata price.
+///
+/// The fair pubdata price tracks the L1 data-posting cost, which can spike independently of
+/// execution gas; this interval governs how often its upper limit is refreshed.
+///
+/// This value is read through the `ParametersStore`, so it reflects any runtime
+/// reconfiguration performed by an administrator. This may be required if existing settings
+/// aren't flexible enough to match the current network price.
+pub fn get_max_pubdata_price_interval() -> Duration {
+    shared_store().read().unwrap().max_pubdata_price_interval
+}
+
+/// Obtains the scaling multiplier for the maximum pubdata price.
+///
+/// Like the gas-price multiplier, the value is validated to be finite and `>= 1.0` once when
+/// the `ParametersStore` is seeded (a sub-one factor would cap the pubdata limit *below* the
+/// observed average and reject healthy postings), and read from the store thereafter so it
+/// reflects any runtime reconfiguration performed by an administrator.
+pub fn get_max_pubdata_price_scale() -> f64 {
+    shared_store().read().unwrap().max_pubdata_price_scale
+}
+
+/// Sets the interval for renewing the maximum pubdata price at runtime.
+pub fn set_max_pubdata_price_interval(interval: Duration) {
+    shared_store().write().unwrap().max_pubdata_price_interval = interval;
+}
+
+/// Sets the scaling multiplier for the maximum pubdata price at runtime.
+///
+/// Returns an error (leaving the stored value untouched) if `scale` is NaN or below the `1.0`
+/// lower bound, so a bad admin request is reported to the RPC caller rather than aborting the
+/// handler.
+pub fn set_max_pubdata_price_scale(scale: f64) -> Result<(), String> {
+    let scale = validate_multiplier(scale)?;
+    shared_store().write().unwrap().max_pubdata_price_scale = scale;
+    Ok(())
+}
+
+/// Obtains the lower bound for the suggested pubdata price.
+///
+/// When `ETH_MIN_PUBDATA_PRICE` is empty or unset, the fixed default floor
+/// `DEFAULT_MIN_PUBDATA_PRICE` (1 gwei) is used; it is not updated from observed L1 costs.
+///
+/// This value is read through the `ParametersStore`, so it reflects any runtime
+/// reconfiguration performed by an administrator.
+pub fn get_min_pubdata_price() -> U256 {
+    shared_store().read().unwrap().min_pubdata_price
+}
+
+/// Sets the lower bound for the suggested pubdata price at runtime.
+pub fn set_min_pubdata_price(min_pubdata_price: U256) {
+    shared_store().write().unwrap().min_pubdata_price = min_pubdata_price;
+}
+
+/// Observation recorded for a single processed block: the gas price actually charged to
+/// users and the realized L1 cost for posting that block's data.
+#[derive(Debug, Clone, Copy)]
+pub struct BlockObservation {
+    /// Gas price charged to users for the block, in wei per gas.
+    pub gas_price_charged: U256,
+    /// Gas used by the block, needed to turn the per-gas price into collected revenue.
+    pub gas_used: U256,
+    /// Realized L1 cost incurred for the block, in wei.
+    pub l1_cost: U256,
+}
+
+impl BlockObservation {
+    /// Total revenue collected for this block, in wei: the per-gas price times the gas used.
+    /// This is what must be compared against `l1_cost` — summing the bare per-gas price would
+    /// mix a per-gas quantity with a total one and make the profit ratio meaningless.
+    fn revenue(&self) -> U256 {
+        self.gas_price_charged.saturating_mul(self.gas_used)
+    }
+}
+
+/// State backing the adaptive, profitability-driven gas price updater.
+///
+/// Holds a bounded ring buffer of recent block observations and the last suggested price, so
+/// that each renewal tick can nudge the price toward one that just covers L1 costs.
+#[derive(Debug, Default)]
+struct AdaptiveState {
+    observations: VecDeque<BlockObservation>,
+    suggested_price: Option<U256>,
+}
+
+static ADAPTIVE_STATE: OnceLock<Mutex<AdaptiveState>> = OnceLock::new();
+
+fn adaptive_state() -> &'static Mutex<AdaptiveState> {
+    ADAPTIVE_STATE.get_or_init(|| Mutex::new(AdaptiveState::default()))
+}
+
+/// Records an observation for a processed block, trimming the ring buffer to the configured
+/// window length (`ETH_GAS_PRICE_WINDOW_BLOCKS`).
+pub fn record_block_observation(gas_price_charged: U256, gas_used: U256, l1_cost: U256) {
+    let window = parameters_impl::get_window_blocks().max(1);
+    let mut state = adaptive_state().lock().unwrap();
+    state.observations.push_back(BlockObservation {
+        gas_price_charged,
+        gas_used,
+        l1_cost,
+    });
+    while state.observations.len() > window {
+        state.observations.pop_front();
+    }
+}
+
+/// Computes the adaptive gas price from recent profitability, clamped to the `[min, max]`
+/// band.
+///
+/// The running profit ratio is `collected / spent` over the observation window. If recent
+/// activity was unprofitable (`ratio < 1`), the suggested price is increased by a bounded
+/// step proportional to the shortfall (capped at `ETH_GAS_PRICE_ADJUST_UP_PCT`). If
+/// profitable (`ratio > 1`) and above `current_avg`, it decays back toward `current_avg` by
+/// the smaller `ETH_GAS_PRICE_ADJUST_DOWN_PCT` step. The result is always clamped into
+/// `[get_min_price(), current_avg * get_max_price_multiplier()]`.
+pub fn get_adaptive_price(current_avg: U256) -> U256 {
+    let up_pct = parameters_impl::get_adjust_up_pct();
+    let down_pct = parameters_impl::get_adjust_down_pct();
+
+    let min_price = get_min_price();
+    let max_price = scale_u256(current_avg, get_max_price_multiplier());
+
+    let mut state = adaptive_state().lock().unwrap();
+    let base = state.suggested_price.unwrap_or(current_avg);
+    let ratio = profit_ratio(&state.observations);
+
+    let clamped = adaptive_price(
+        base, current_avg, ratio, up_pct, down_pct, min_price, max_price,
+    );
+    state.suggested_price = Some(clamped);
+    clamped
 }
 
-/// Obtains the scaling factor for the maximum gas price.
+/// Pure core of the adaptive updater: given the previously suggested price, the sampled
+/// average and the current profit ratio, returns the next suggested price clamped to
+/// `[min_price, max_price]`.
+fn adaptive_price(
+    base: U256,
+    current_avg: U256,
+    ratio: Option<f64>,
+    up_pct: f64,
+    down_pct: f64,
+    min_price: U256,
+    max_price: U256,
+) -> U256 {
+    let suggested = match ratio {
+        // Not enough data yet: fall back to the sampled average.
+        None => current_avg,
+        // Unprofitable: raise the price proportionally to the shortfall, bounded by `up_pct`.
+        Some(ratio) if ratio < 1.0 => {
+            let shortfall = (1.0 - ratio).min(1.0);
+            scale_u256(base, 1.0 + up_pct * shortfall)
+        }
+        // Profitable and above the average: decay toward the average by the smaller `down_pct` step.
+        Some(ratio) if ratio > 1.0 && base > current_avg => {
+            let decay = scale_u256(base - current_avg, down_pct);
+            base.saturating_sub(decay)
+        }
+        // Exactly break-even, or profitable at or below the average: leave the price untouched.
+        Some(_) => base,
+    };
+
+    clamp_price(suggested, min_price, max_price)
+}
+
+/// Computes the running profit ratio (`collected / spent`) over the observation window, or
+/// `None` if there are no observations or nothing was spent.
 ///
-/// This value is not cached internally, as it may be changed for the already running
-/// server by an administrator. This may be required if existing settings aren't flexible
-/// enough to match the current network price.
-pub fn get_max_price_scale() -> f64 {
-    parameters_impl::get_max_price_scale()
+/// `collected` accumulates per-block *revenue* (price times gas used), not the bare per-gas
+/// price, so both sides of the ratio are total-wei quantities and the comparison is meaningful.
+fn profit_ratio(observations: &VecDeque<BlockObservation>) -> Option<f64> {
+    if observations.is_empty() {
+        return None;
+    }
+
+    let mut collected = U256::zero();
+    let mut spent = U256::zero();
+    for observation in observations {
+        collected = collected.saturating_add(observation.revenue());
+        spent = spent.saturating_add(observation.l1_cost);
+    }
+
+    if spent.is_zero() {
+        return None;
+    }
+
+    Some(u256_to_f64(collected) / u256_to_f64(spent))
+}
+
+/// Clamps `price` into the inclusive `[min, max]` band. When the band is inverted (a
+/// misconfiguration), the maximum wins so the upper limit is always respected.
+fn clamp_price(price: U256, min: U256, max: U256) -> U256 {
+    price.max(min).min(max)
+}
+
+/// Multiplies a `U256` by an `f64` factor with fixed-point precision, saturating on overflow.
+fn scale_u256(value: U256, factor: f64) -> U256 {
+    const PRECISION: u64 = 1_000_000_000;
+    let scaled_factor = (factor.max(0.0) * PRECISION as f64) as u64;
+    value.saturating_mul(U256::from(scaled_factor)) / U256::from(PRECISION)
+}
+
+/// Converts a `U256` to an `f64`, used only for computing ratios where loss of precision on
+/// very large values is acceptable.
+fn u256_to_f64(value: U256) -> f64 {
+    let mut result = 0.0f64;
+    for word in value.0.iter().rev() {
+        result = result * (u64::MAX as f64 + 1.0) + *word as f64;
+    }
+    result
+}
+
+/// Number of wei in one ether.
+const WEI_PER_ETH: u64 = 1_000_000_000_000_000_000;
+
+/// ETH/USD rate used by `auto` mode when `ETH_USD_DEFAULT_RATE` is unset, blank or
+/// unparseable. Operators can override it via `ETH_USD_DEFAULT_RATE`.
+const DEFAULT_ETH_USD_RATE: f64 = 2000.0;
+
+/// Resolves the ETH/USD rate from an optional override, falling back to `default_rate` when the
+/// override is absent or blank.
+///
+/// This is the `auto`-mode path: it never reads an *unset* override, so enabling USD mode
+/// without `ETH_USD_PER_ETH` degrades to the configured default instead of panicking.
+fn resolve_eth_usd_rate(override_raw: Option<&str>, default_rate: f64) -> f64 {
+    match override_raw {
+        Some(raw) if !raw.trim().is_empty() => raw.trim().parse().unwrap_or(default_rate),
+        _ => default_rate,
+    }
+}
+
+/// Converts a USD per-transaction budget and an ETH/USD rate into a wei-denominated per-gas
+/// ceiling for a transaction expected to consume `gas_used` units of gas.
+///
+/// Returns `U256::max_value()` (no effective cap) for a zero `gas_used` or a non-positive rate,
+/// so a missing quote never clamps the suggested price down to zero. The budget is scaled in
+/// `U256` arithmetic (see `scale_u256`), so large fiat budgets are not silently truncated or
+/// saturated to a `u64` ceiling.
+fn usd_calibrated_price(usd_per_tx: f64, eth_usd: f64, gas_used: U256) -> U256 {
+    if gas_used.is_zero() || !eth_usd.is_finite() || eth_usd <= 0.0 || !usd_per_tx.is_finite() {
+        return U256::max_value();
+    }
+
+    // Budget in ether, then scale one ether (in wei) by it to stay in integer arithmetic.
+    let eth_budget = usd_per_tx / eth_usd;
+    let wei_budget = scale_u256(U256::from(WEI_PER_ETH), eth_budget);
+
+    wei_budget / gas_used
 }
 
 // Actual methods implementation for non-test purposes.
 #[cfg(not(test))]
 mod parameters_impl {
     // Built-in deps.
-    use std::time::Duration;
+    use std::sync::{Mutex, OnceLock};
+    use std::time::{Duration, Instant};
     // Workspace deps
     use models::config_options::parse_env;
+    use web3::types::U256;
 
     /// Name of the environment variable responsible for the `max_gas_price` renewing interval.
     const MAX_GAS_PRICE_RENEWAL_INTERVAL_VAR: &'static str = "ETH_MAX_GAS_PRICE_RENEWAL_INTERVAL";
-    /// Name of the environment variable responsible for the `max_gas_price` scaling multiplier.
+    /// Name of the environment variable responsible for the `max_gas_price` multiplier.
     const MAX_GAS_PRICE_SCALE_FACTOR_VAR: &'static str = "ETH_MAX_GAS_PRICE_SCALE_FACTOR";
+    /// Name of the environment variable holding the lower bound for the suggested gas price,
+    /// in wei. Empty or unset means "no floor".
+    const MIN_GAS_PRICE_VAR: &'static str = "ETH_MIN_GAS_PRICE";
+    /// Name of the environment variable selecting how the upper gas price limit is derived.
+    /// Accepts `scale` (default, multiplicative) or `usd` (USD-calibrated).
+    const MAX_GAS_PRICE_MODE_VAR: &'static str = "ETH_MAX_GAS_PRICE_MODE";
+    /// Name of the environment variable holding the target per-transaction cost, in USD.
+    const MAX_GAS_PRICE_USD_PER_TX_VAR: &'static str = "ETH_MAX_GAS_PRICE_USD_PER_TX";
+    /// Name of the environment variable holding a fixed ETH/USD rate override. When unset,
+    /// the `auto` rate (see `ETH_USD_DEFAULT_RATE`) is used, re-read at most once per
+    /// `ETH_USD_PRICE_UPDATE_PERIOD`.
+    const ETH_USD_PER_ETH_VAR: &'static str = "ETH_USD_PER_ETH";
+    /// Name of the environment variable holding how often, in seconds, the ETH/USD quote is
+    /// refreshed in `auto` mode.
+    const ETH_USD_PRICE_UPDATE_PERIOD_VAR: &'static str = "ETH_USD_PRICE_UPDATE_PERIOD";
+    /// Name of the environment variable holding the ETH/USD rate used by the `auto` mode.
+    /// Empty or unset means "use the built-in `DEFAULT_ETH_USD_RATE`".
+    const ETH_USD_DEFAULT_RATE_VAR: &'static str = "ETH_USD_DEFAULT_RATE";
+
+    /// Default ETH/USD quote refresh period, in seconds, used when `ETH_USD_PRICE_UPDATE_PERIOD`
+    /// is unset. Chosen as a conservative once-a-minute refresh.
+    const DEFAULT_ETH_USD_PRICE_UPDATE_PERIOD_SECS: u64 = 60;
+    /// Name of the environment variable holding the maximum upward adjustment per tick, as a
+    /// fraction (e.g. `0.2` for up to +20%), used by the adaptive updater.
+    const GAS_PRICE_ADJUST_UP_PCT_VAR: &'static str = "ETH_GAS_PRICE_ADJUST_UP_PCT";
+    /// Name of the environment variable holding the maximum downward adjustment per tick, as a
+    /// fraction, used by the adaptive updater.
+    const GAS_PRICE_ADJUST_DOWN_PCT_VAR: &'static str = "ETH_GAS_PRICE_ADJUST_DOWN_PCT";
+    /// Name of the environment variable holding the length, in blocks, of the adaptive
+    /// updater's observation window.
+    const GAS_PRICE_WINDOW_BLOCKS_VAR: &'static str = "ETH_GAS_PRICE_WINDOW_BLOCKS";
+    /// Name of the environment variable responsible for the `max_pubdata_price` renewing interval.
+    const MAX_PUBDATA_PRICE_RENEWAL_INTERVAL_VAR: &'static str =
+        "ETH_MAX_PUBDATA_PRICE_RENEWAL_INTERVAL";
+    /// Name of the environment variable responsible for the `max_pubdata_price` scaling multiplier.
+    const MAX_PUBDATA_PRICE_SCALE_FACTOR_VAR: &'static str = "ETH_MAX_PUBDATA_PRICE_SCALE_FACTOR";
+    /// Name of the environment variable holding the lower bound for the suggested pubdata
+    /// price, in wei. Empty or unset means "use the default floor".
+    const MIN_PUBDATA_PRICE_VAR: &'static str = "ETH_MIN_PUBDATA_PRICE";
+
+    /// Default lower bound for the pubdata price when `ETH_MIN_PUBDATA_PRICE` is unset.
+    ///
+    /// Chosen as a conservative approximation of recent L1 calldata costs (1 gwei). It is a
+    /// fixed value and is not updated from observed costs.
+    const DEFAULT_MIN_PUBDATA_PRICE: u64 = 1_000_000_000;
+
+    /// Cached ETH/USD quote used by the `auto` rate mode, together with the instant at which
+    /// it was observed, so that the live quote is refreshed at most once per update period.
+    static ETH_USD_QUOTE_CACHE: OnceLock<Mutex<Option<(Instant, f64)>>> = OnceLock::new();
 
-    /// Obtains the interval for renewing the maximum gas price.
+    /// Obtains the interval for renewing the maximum gas price from the environment.
     ///
-    /// This value is not cached internally, as it may be changed for the already running
-    /// server by an administrator. This may be required if existing settings aren't flexible
-    /// enough to match the current network price.
+    /// This is a seed value read once into the `ParametersStore`; runtime reconfiguration goes
+    /// through `set_max_price_interval` rather than re-reading the environment.
     pub fn get_max_price_interval() -> Duration {
         let renew_interval: u64 = parse_env(MAX_GAS_PRICE_RENEWAL_INTERVAL_VAR);
 
         Duration::from_secs(renew_interval)
     }
 
-    /// Obtains the scaling factor for the maximum gas price.
+    /// Obtains the raw multiplier for the maximum gas price from the environment.
     ///
-    /// This value is not cached internally, as it may be changed for the already running
-    /// server by an administrator. This may be required if existing settings aren't flexible
-    /// enough to match the current network price.
-    pub fn get_max_price_scale() -> f64 {
+    /// The value is validated (finite, `>= 1.0`) by the `ParametersStore` when it is loaded;
+    /// see `validate_multiplier`.
+    pub fn get_max_price_multiplier() -> f64 {
         parse_env(MAX_GAS_PRICE_SCALE_FACTOR_VAR)
     }
+
+    /// Obtains the lower bound for the suggested gas price.
+    ///
+    /// An empty or unset `ETH_MIN_GAS_PRICE` means "no floor" and yields zero.
+    pub fn get_min_price() -> U256 {
+        match std::env::var(MIN_GAS_PRICE_VAR) {
+            Ok(ref raw) if !raw.is_empty() => U256::from_dec_str(raw)
+                .unwrap_or_else(|_| panic!("Failed to parse {}", MIN_GAS_PRICE_VAR)),
+            _ => U256::zero(),
+        }
+    }
+
+    /// Obtains the interval for renewing the maximum pubdata price from the environment.
+    pub fn get_max_pubdata_price_interval() -> Duration {
+        let renew_interval: u64 = parse_env(MAX_PUBDATA_PRICE_RENEWAL_INTERVAL_VAR);
+
+        Duration::from_secs(renew_interval)
+    }
+
+    /// Obtains the raw scaling multiplier for the maximum pubdata price from the environment.
+    ///
+    /// The value is validated (finite, `>= 1.0`) once when the `ParametersStore` is seeded;
+    /// see `validate_multiplier`.
+    pub fn get_max_pubdata_price_scale() -> f64 {
+        parse_env(MAX_PUBDATA_PRICE_SCALE_FACTOR_VAR)
+    }
+
+    /// Obtains the lower bound for the suggested pubdata price.
+    ///
+    /// An empty or unset `ETH_MIN_PUBDATA_PRICE` falls back to `DEFAULT_MIN_PUBDATA_PRICE`.
+    pub fn get_min_pubdata_price() -> U256 {
+        match std::env::var(MIN_PUBDATA_PRICE_VAR) {
+            Ok(ref raw) if !raw.is_empty() => U256::from_dec_str(raw)
+                .unwrap_or_else(|_| panic!("Failed to parse {}", MIN_PUBDATA_PRICE_VAR)),
+            _ => U256::from(DEFAULT_MIN_PUBDATA_PRICE),
+        }
+    }
+
+    /// Obtains the maximum upward adjustment per tick for the adaptive updater.
+    pub fn get_adjust_up_pct() -> f64 {
+        parse_env(GAS_PRICE_ADJUST_UP_PCT_VAR)
+    }
+
+    /// Obtains the maximum downward adjustment per tick for the adaptive updater.
+    pub fn get_adjust_down_pct() -> f64 {
+        parse_env(GAS_PRICE_ADJUST_DOWN_PCT_VAR)
+    }
+
+    /// Obtains the length, in blocks, of the adaptive updater's observation window.
+    pub fn get_window_blocks() -> usize {
+        parse_env(GAS_PRICE_WINDOW_BLOCKS_VAR)
+    }
+
+    /// Obtains the upper limit for the gas price, choosing between the multiplicative scaling
+    /// and the USD-calibrated path based on `ETH_MAX_GAS_PRICE_MODE`.
+    ///
+    /// In USD mode an unset or blank `ETH_MAX_GAS_PRICE_USD_PER_TX` means "no fiat cap" and
+    /// yields `U256::max_value()`, so enabling the mode before a budget is configured degrades
+    /// gracefully rather than panicking.
+    pub fn get_max_price_limit(gas_used: U256) -> U256 {
+        let usd_mode = std::env::var(MAX_GAS_PRICE_MODE_VAR)
+            .map(|mode| mode.eq_ignore_ascii_case("usd"))
+            .unwrap_or(false);
+
+        if !usd_mode {
+            // No fiat cap in scale mode: the caller applies `get_max_price_multiplier` to the
+            // sampled average itself.
+            return U256::max_value();
+        }
+
+        let usd_per_tx = match parse_optional_f64(MAX_GAS_PRICE_USD_PER_TX_VAR) {
+            Some(usd_per_tx) => usd_per_tx,
+            None => return U256::max_value(),
+        };
+        let eth_usd = get_eth_usd_rate();
+
+        super::usd_calibrated_price(usd_per_tx, eth_usd, gas_used)
+    }
+
+    /// Parses an optional `f64` environment variable, returning `None` when it is unset, blank,
+    /// or unparseable, so callers can supply their own fallback instead of panicking.
+    fn parse_optional_f64(var: &str) -> Option<f64> {
+        std::env::var(var)
+            .ok()
+            .and_then(|raw| raw.trim().parse().ok())
+    }
+
+    /// Returns the ETH/USD rate either from the `ETH_USD_PER_ETH` override or, when that is
+    /// unset, from the automatically refreshed quote.
+    ///
+    /// The refresh period defaults to `DEFAULT_ETH_USD_PRICE_UPDATE_PERIOD_SECS` when
+    /// `ETH_USD_PRICE_UPDATE_PERIOD` is unset, so the auto path never panics on a missing var.
+    fn get_eth_usd_rate() -> f64 {
+        match std::env::var(ETH_USD_PER_ETH_VAR) {
+            Ok(raw) => raw
+                .trim()
+                .parse()
+                .unwrap_or_else(|_| panic!("Failed to parse {}", ETH_USD_PER_ETH_VAR)),
+            Err(_) => {
+                let update_period = std::env::var(ETH_USD_PRICE_UPDATE_PERIOD_VAR)
+                    .ok()
+                    .and_then(|raw| raw.trim().parse().ok())
+                    .unwrap_or(DEFAULT_ETH_USD_PRICE_UPDATE_PERIOD_SECS);
+                fetch_eth_usd_quote(Duration::from_secs(update_period))
+            }
+        }
+    }
+
+    /// Returns the latest cached ETH/USD quote, refreshing it from the oracle if the cached
+    /// value is older than `update_period`.
+    fn fetch_eth_usd_quote(update_period: Duration) -> f64 {
+        let cache = ETH_USD_QUOTE_CACHE.get_or_init(|| Mutex::new(None));
+        let mut guard = cache.lock().expect("ETH/USD quote cache is poisoned");
+
+        let is_fresh = guard
+            .map(|(observed_at, _)| observed_at.elapsed() < update_period)
+            .unwrap_or(false);
+
+        if !is_fresh {
+            let quote = query_eth_usd_oracle();
+            *guard = Some((Instant::now(), quote));
+        }
+
+        guard.expect("ETH/USD quote must be populated").1
+    }
+
+    /// Returns the ETH/USD rate used by the `auto` mode.
+    ///
+    /// The rate is the configured `ETH_USD_DEFAULT_RATE` (or the built-in `DEFAULT_ETH_USD_RATE`);
+    /// no external price source is queried, so the rate does not follow the market.
+    /// Crucially this does *not* read the unset `ETH_USD_PER_ETH` override, so enabling `auto`
+    /// mode without an override degrades gracefully rather than panicking.
+    fn query_eth_usd_oracle() -> f64 {
+        super::resolve_eth_usd_rate(
+            std::env::var(ETH_USD_DEFAULT_RATE_VAR).ok().as_deref(),
+            super::DEFAULT_ETH_USD_RATE,
+        )
+    }
 }
 
 // Hard-coded implementation for tests.
@@ -71,6 +681,8 @@ mod parameters_impl {
 mod parameters_impl {
     // Built-in deps.
     use std::time::Duration;
+    // Workspace deps
+    use web3::types::U256;
 
     /// `get_max_price_interval` version for tests not looking for an environment variable value
     /// but using a zero interval instead.
@@ -78,9 +690,176 @@ mod parameters_impl {
         Duration::from_secs(0)
     }
 
-    /// `get_max_price_scale` version for tests not looking for an environment variable value
-    /// but using a fixed scale factor (1.5) instead.
-    pub fn get_max_price_scale() -> f64 {
+    /// `get_max_price_multiplier` version for tests not looking for an environment variable value
+    /// but using a fixed multiplier (1.5) instead.
+    pub fn get_max_price_multiplier() -> f64 {
         1.5f64
     }
+
+    /// `get_max_price_limit` version for tests using a fixed USD budget ($2.0 per tx) and a
+    /// fixed ETH/USD rate ($2000) instead of reading the environment.
+    pub fn get_max_price_limit(gas_used: U256) -> U256 {
+        super::usd_calibrated_price(2.0f64, 2000.0f64, gas_used)
+    }
+
+    /// `get_min_price` version for tests not looking for an environment variable value but
+    /// using a fixed floor (1 gwei) instead.
+    pub fn get_min_price() -> U256 {
+        U256::from(1_000_000_000u64)
+    }
+
+    /// `get_adjust_up_pct` version for tests using a fixed maximum upward step (20%).
+    pub fn get_adjust_up_pct() -> f64 {
+        0.2f64
+    }
+
+    /// `get_adjust_down_pct` version for tests using a fixed maximum downward step (5%).
+    pub fn get_adjust_down_pct() -> f64 {
+        0.05f64
+    }
+
+    /// `get_window_blocks` version for tests using a fixed observation window (10 blocks).
+    pub fn get_window_blocks() -> usize {
+        10
+    }
+
+    /// `get_max_pubdata_price_interval` version for tests using a zero interval instead.
+    pub fn get_max_pubdata_price_interval() -> Duration {
+        Duration::from_secs(0)
+    }
+
+    /// `get_max_pubdata_price_scale` version for tests using a fixed scale factor (1.5) instead.
+    pub fn get_max_pubdata_price_scale() -> f64 {
+        1.5f64
+    }
+
+    /// `get_min_pubdata_price` version for tests using a fixed floor (1 gwei) instead.
+    pub fn get_min_pubdata_price() -> U256 {
+        U256::from(1_000_000_000u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// At $2 per tx, $2000/ETH and 21000 gas the ceiling is `(2/2000) ETH / 21000` per gas.
+    #[test]
+    fn usd_calibrated_price_divides_budget_over_gas() {
+        let gas_used = U256::from(21_000u64);
+        let price = usd_calibrated_price(2.0, 2000.0, gas_used);
+
+        let expected = U256::from((0.001 * WEI_PER_ETH as f64) as u64) / gas_used;
+        assert_eq!(price, expected);
+    }
+
+    /// A zero `gas_used` or a non-positive rate leaves the limit effectively uncapped instead of
+    /// collapsing to zero.
+    #[test]
+    fn usd_calibrated_price_guards_degenerate_inputs() {
+        assert_eq!(usd_calibrated_price(2.0, 2000.0, U256::zero()), U256::max_value());
+        assert_eq!(usd_calibrated_price(2.0, 0.0, U256::from(21_000u64)), U256::max_value());
+    }
+
+    /// `auto` mode (no override) resolves to the configured default rather than panicking,
+    /// which is the regression the oracle fallback fixes.
+    #[test]
+    fn resolve_eth_usd_rate_falls_back_when_override_absent() {
+        assert_eq!(resolve_eth_usd_rate(None, 1800.0), 1800.0);
+        assert_eq!(resolve_eth_usd_rate(Some("   "), 1800.0), 1800.0);
+        assert_eq!(resolve_eth_usd_rate(Some("not-a-number"), 1800.0), 1800.0);
+        assert_eq!(resolve_eth_usd_rate(Some(" 2500.5 "), 1800.0), 2500.5);
+    }
+
+    const GWEI: u64 = 1_000_000_000;
+
+    fn observation(price_gwei: u64, gas_used: u64, l1_cost_gwei: u64) -> BlockObservation {
+        BlockObservation {
+            gas_price_charged: U256::from(price_gwei) * U256::from(GWEI),
+            gas_used: U256::from(gas_used),
+            l1_cost: U256::from(l1_cost_gwei) * U256::from(GWEI),
+        }
+    }
+
+    /// The ratio must compare revenue (price * gas) against L1 cost. A per-gas price of 50 gwei
+    /// over 1,000,000 gas collects 50,000,000 gwei and should read as profitable against a
+    /// 25,000,000 gwei L1 cost — the bug summed the bare per-gas price and read every window as a loss.
+    #[test]
+    fn profit_ratio_uses_revenue_not_bare_price() {
+        let mut obs = VecDeque::new();
+        obs.push_back(observation(50, 1_000_000, 25_000_000));
+        let ratio = profit_ratio(&obs).expect("ratio is defined");
+        assert!((ratio - 2.0).abs() < 1e-6, "expected ~2.0, got {}", ratio);
+    }
+
+    #[test]
+    fn profit_ratio_none_without_observations_or_spend() {
+        assert!(profit_ratio(&VecDeque::new()).is_none());
+        let mut obs = VecDeque::new();
+        obs.push_back(observation(50, 1_000_000, 0));
+        assert!(profit_ratio(&obs).is_none());
+    }
+
+    /// When unprofitable the price rises, bounded by `up_pct`, and the result is clamped to the
+    /// configured band.
+    #[test]
+    fn adaptive_price_raises_when_unprofitable() {
+        let base = U256::from(100u64);
+        let next = adaptive_price(
+            base,
+            base,
+            Some(0.5),
+            0.2,
+            0.05,
+            U256::zero(),
+            U256::from(1000u64),
+        );
+        // shortfall 0.5 -> +0.2 * 0.5 = +10% -> 110.
+        assert_eq!(next, U256::from(110u64));
+    }
+
+    /// A profitable ratio decays the price toward the average, but never below the floor.
+    #[test]
+    fn adaptive_price_decays_when_profitable_and_respects_floor() {
+        let next = adaptive_price(
+            U256::from(200u64),
+            U256::from(100u64),
+            Some(2.0),
+            0.2,
+            0.05,
+            U256::from(150u64),
+            U256::from(1000u64),
+        );
+        // decay 5% of (200-100)=5 -> 195, still above the 150 floor.
+        assert_eq!(next, U256::from(195u64));
+    }
+
+    #[test]
+    fn clamp_price_honours_band() {
+        assert_eq!(clamp_price(U256::from(5u64), U256::from(10u64), U256::from(20u64)), U256::from(10u64));
+        assert_eq!(clamp_price(U256::from(25u64), U256::from(10u64), U256::from(20u64)), U256::from(20u64));
+        assert_eq!(clamp_price(U256::from(15u64), U256::from(10u64), U256::from(20u64)), U256::from(15u64));
+    }
+
+    #[test]
+    fn scale_u256_applies_factor() {
+        assert_eq!(scale_u256(U256::from(1000u64), 1.5), U256::from(1500u64));
+        assert_eq!(scale_u256(U256::from(1000u64), 1.0), U256::from(1000u64));
+    }
+
+    /// The public pubdata-scale getter routes the fixture through `validate_multiplier`, so a
+    /// valid (`>= 1.0`) factor passes through unchanged.
+    #[test]
+    fn pubdata_price_scale_passes_validation() {
+        assert_eq!(get_max_pubdata_price_scale(), 1.5);
+    }
+
+    #[test]
+    fn validate_multiplier_rejects_sub_one_and_nan() {
+        assert_eq!(validate_multiplier(1.0), Ok(1.0));
+        assert_eq!(validate_multiplier(2.5), Ok(2.5));
+        assert!(validate_multiplier(0.5).is_err());
+        assert!(validate_multiplier(-1.0).is_err());
+        assert!(validate_multiplier(f64::NAN).is_err());
+    }
 }